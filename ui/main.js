@@ -1,24 +1,73 @@
 const el = {
   tone3000Key: document.getElementById("tone3000Key"),
   geminiKey: document.getElementById("geminiKey"),
+  keysUnlockRow: document.getElementById("keysUnlockRow"),
+  keysPassphrase: document.getElementById("keysPassphrase"),
+  keysUnlockButton: document.getElementById("keysUnlockButton"),
   geminiModel: document.getElementById("geminiModel"),
+  uiLanguage: document.getElementById("uiLanguage"),
+  privacyMode: document.getElementById("privacyMode"),
+  ownedPreference: document.getElementById("ownedPreference"),
+  dedupeMode: document.getElementById("dedupeMode"),
+  micPreference: document.getElementById("micPreference"),
+  uniformFileNames: document.getElementById("uniformFileNames"),
+  fastPlan: document.getElementById("fastPlan"),
+  finalistDetails: document.getElementById("finalistDetails"),
+  excludeRoomIrs: document.getElementById("excludeRoomIrs"),
+  auditPrompts: document.getElementById("auditPrompts"),
+  templateSelect: document.getElementById("templateSelect"),
   toneRequest: document.getElementById("toneRequest"),
+  requestSuggestions: document.getElementById("requestSuggestions"),
   outputDir: document.getElementById("outputDir"),
+  defaultOutputButton: document.getElementById("defaultOutputButton"),
   maxTones: document.getElementById("maxTones"),
   maxResults: document.getElementById("maxResults"),
+  callEstimate: document.getElementById("callEstimate"),
+  accountInfo: document.getElementById("accountInfo"),
+  resumePanel: document.getElementById("resumePanel"),
+  resumeList: document.getElementById("resumeList"),
+  warningPanel: document.getElementById("warningPanel"),
+  warningList: document.getElementById("warningList"),
   runButton: document.getElementById("runButton"),
+  previewButton: document.getElementById("previewButton"),
+  surpriseGenre: document.getElementById("surpriseGenre"),
+  surpriseButton: document.getElementById("surpriseButton"),
   clearLogsButton: document.getElementById("clearLogsButton"),
   statusText: document.getElementById("statusText"),
   runState: document.getElementById("runState"),
+  progressText: document.getElementById("progressText"),
   analysisSummary: document.getElementById("analysisSummary"),
   analysisMeta: document.getElementById("analysisMeta"),
   aiStepList: document.getElementById("aiStepList"),
+  shareButton: document.getElementById("shareButton"),
   selectedToneList: document.getElementById("selectedToneList"),
   modelList: document.getElementById("modelList"),
   logOutput: document.getElementById("logOutput"),
+  libraryStats: document.getElementById("libraryStats"),
+  browseGear: document.getElementById("browseGear"),
+  trendPeriod: document.getElementById("trendPeriod"),
+  trendingButton: document.getElementById("trendingButton"),
+  browseList: document.getElementById("browseList"),
+  newArrivalsButton: document.getElementById("newArrivalsButton"),
+  browseMoreButton: document.getElementById("browseMoreButton"),
+  toneDetail: document.getElementById("toneDetail"),
+  dailyPanel: document.getElementById("dailyPanel"),
+  dailyTone: document.getElementById("dailyTone"),
+  dailyDownloadButton: document.getElementById("dailyDownloadButton"),
+  trayMode: document.getElementById("trayMode"),
+  libraryRefreshButton: document.getElementById("libraryRefreshButton"),
 };
 
-let isRunning = false;
+const MAX_PARALLEL_RUNS = 2;
+let runningCount = 0;
+let templateOptions = {};
+let dailyTone = null;
+let newArrivals = { page: 0, tones: [] };
+let suggestTimer = null;
+let lastRun = null;
+let lastRigs = [];
+// Job whose streamed events the log panel shows: the most recently started one.
+let displayedJobId = null;
 
 function escapeHtml(value) {
   return String(value)
@@ -27,6 +76,19 @@ function escapeHtml(value) {
     .replaceAll(">", "&gt;");
 }
 
+const AUTH_GUIDANCE = {
+  key_revoked: "TONE3000 anahtari iptal edilmis ya da gecersiz. tone3000.com'da yeni anahtar olusturup keys.txt'yi veya anahtar alanini guncelle.",
+  missing_scope: "TONE3000 anahtarinin indirme yetkisi yok. tone3000.com'da bu anahtara indirme iznini ver ya da yetkili yeni bir anahtar olustur.",
+  service_unreachable: "TONE3000'e ulasilamiyor. Internet baglantini veya proxy ayarini kontrol edip tekrar dene.",
+  auth_failed: "TONE3000 girisi basarisiz oldu. Tekrar dene; surerse anahtari kontrol et.",
+};
+
+// Backend auth errors carry an `(auth:<code>)` marker; `run_download` also returns it as `error_code`.
+function describeError(error, code) {
+  const authCode = code || /\(auth:([a-z_]+)\)/.exec(error || "")?.[1];
+  return AUTH_GUIDANCE[authCode] ? `${AUTH_GUIDANCE[authCode]} (${error})` : error;
+}
+
 function setRunState(kind, text) {
   el.runState.className = `run-state ${kind}`;
   el.runState.textContent = kind === "running" ? "Running" : kind.charAt(0).toUpperCase() + kind.slice(1);
@@ -70,6 +132,30 @@ function renderAiSteps(steps) {
     .join("");
 }
 
+function renderComparison(rows) {
+  if (!rows || rows.length < 2) return "";
+  const body = rows
+    .map(
+      (row) => `
+        <tr class="${row.selected ? "picked" : ""}">
+          <td title="${escapeHtml(row.reason || "")}">${escapeHtml(row.title || "")}</td>
+          <td>${escapeHtml(row.gain_character || "")}</td>
+          <td>${row.downloads ?? 0}</td>
+          <td>${escapeHtml(row.platform || "")}</td>
+          <td>${escapeHtml(row.creator || "")}</td>
+        </tr>`
+    )
+    .join("");
+  return `
+    <details class="comparison">
+      <summary>Finalistler (${rows.length})</summary>
+      <table>
+        <thead><tr><th>Ton</th><th>Gain</th><th>Indirme</th><th>Platform</th><th>Yazar</th></tr></thead>
+        <tbody>${body}</tbody>
+      </table>
+    </details>`;
+}
+
 function renderTones(rigs, fallbackTones) {
   if (rigs && rigs.length > 0) {
     el.selectedToneList.className = "tone-list";
@@ -79,11 +165,52 @@ function renderTones(rigs, fallbackTones) {
       <article class="tone-item">
         <div class="name">${escapeHtml(rig.preset || "Preset")} - ${escapeHtml(rig.amp?.title || "Amp")}</div>
         <div class="meta">
-          Amp: ${escapeHtml(rig.amp?.gear || "amp")} - ${escapeHtml(rig.amp?.platform || "unknown")}
+          Amp: ${escapeHtml(rig.head_gear || rig.amp?.gear || "amp")} - ${escapeHtml(rig.amp?.platform || "unknown")}
         </div>
         <div class="meta">
           Cab: ${rig.cab ? escapeHtml(rig.cab.title || "Cab/IR") : "Gerekmiyor"}
         </div>
+        ${
+          rig.tuning
+            ? `<div class="meta">Akort: ${escapeHtml(rig.tuning)} - Manyetik: ${escapeHtml(rig.pickup || "-")}</div>`
+            : ""
+        }
+        ${
+          (rig.eq_suggestions || []).length
+            ? `<div class="meta">EQ: ${escapeHtml(rig.eq_suggestions.join(", "))}</div>`
+            : ""
+        }
+        ${(rig.components || [])
+          .filter((c) => !["amp", "rig", "bass_amp", "cab"].includes(c.role))
+          .map(
+            (c) => `<div class="meta">${escapeHtml(c.role)}: ${escapeHtml(c.tone?.title || c.purpose || "")}</div>`
+          )
+          .join("")}
+        ${
+          rig.needs_review
+            ? `<div class="meta review">Dusuk guven, elle kontrol onerilir: ${escapeHtml(
+                (rig.review_reasons || []).join("; ")
+              )}</div>`
+            : ""
+        }
+        ${
+          rig.complete === false
+            ? `<div class="meta review">Eksik: ${escapeHtml(
+                (rig.missing || []).map((gap) => `${gap.role} (${gap.reason})`).join("; ")
+              )}</div>`
+            : ""
+        }
+        ${
+          lastRun?.runId && (rig.components || []).length
+            ? `<div class="meta-row">${rig.components
+                .map(
+                  (c) =>
+                    `<button type="button" class="meta-chip" data-reroll-preset="${escapeHtml(rig.preset || "")}" data-reroll-role="${escapeHtml(c.role)}">${escapeHtml(c.role)} yeniden sec</button>`
+                )
+                .join("")}</div>`
+            : ""
+        }
+        ${renderComparison(rig.comparison)}
       </article>
     `
       )
@@ -115,6 +242,129 @@ function renderTones(rigs, fallbackTones) {
     .join("");
 }
 
+// Re-picks one component of the last run from its stored candidate pool.
+async function onRigListClick(event) {
+  const invoke = getInvoke();
+  const button = event.target?.closest?.("[data-reroll-role]");
+  if (!invoke || !button || !lastRun) return;
+  const { rerollPreset: preset, rerollRole: role } = button.dataset;
+  button.disabled = true;
+  el.progressText.textContent = `${preset} ${role} yeniden seciliyor...`;
+  try {
+    const result = await invoke("reroll_component", {
+      ...lastRun,
+      preset,
+      role,
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+      geminiApiKey: el.geminiKey.value.trim() || null,
+    });
+    const rig = lastRigs.find((r) => r.preset === result.preset);
+    if (rig) {
+      const component = (rig.components || []).find((c) => c.role === result.role);
+      if (component) {
+        component.tone = result.tone;
+        component.selection_reason = result.selection_reason;
+      }
+      if (rig.amp?.id === result.replaced?.id) rig.amp = result.tone;
+      if (rig.cab?.id === result.replaced?.id) rig.cab = result.tone;
+      renderTones(lastRigs, []);
+    }
+    el.progressText.textContent = `${result.preset} ${result.role}: ${result.tone?.title || ""} (${result.downloaded_count} model indirildi)`;
+  } catch (err) {
+    button.disabled = false;
+    el.progressText.textContent = typeof err === "string" ? err : "Bilesen yeniden secilemedi.";
+  }
+}
+
+function renderBrowseList(tones, emptyText) {
+  if (!tones || tones.length === 0) {
+    el.browseList.className = "tone-list empty";
+    el.browseList.textContent = emptyText;
+    return;
+  }
+  el.browseList.className = "tone-list";
+  el.browseList.innerHTML = tones
+    .map(
+      (tone) => `
+      <article class="tone-item" data-tone-id="${escapeHtml(tone.id ?? "")}">
+        <div class="name">${escapeHtml(tone.title || "Untitled Tone")}</div>
+        <div class="meta">
+          ${escapeHtml(tone.gear || "unknown")} - ${escapeHtml(tone.author || "-")} - ${tone.downloads_count ?? 0} indirme
+        </div>
+      </article>
+    `
+    )
+    .join("");
+}
+
+async function onTrending() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  el.browseList.className = "tone-list empty";
+  el.browseList.textContent = "Yukleniyor...";
+  el.browseMoreButton.hidden = true;
+  try {
+    const result = await invoke("get_trending", {
+      gear: el.browseGear.value || null,
+      period: el.trendPeriod.value,
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+    });
+    renderBrowseList(result.tones, "Bu donem icin trend bulunamadi.");
+  } catch (err) {
+    renderBrowseList([], typeof err === "string" ? err : "Trendler alinamadi.");
+  }
+}
+
+async function loadNewArrivals(nextPage) {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  if (nextPage === 1) {
+    newArrivals = { page: 0, tones: [] };
+    el.browseList.className = "tone-list empty";
+    el.browseList.textContent = "Yukleniyor...";
+  }
+  try {
+    const result = await invoke("get_new_arrivals", {
+      gear: el.browseGear.value || null,
+      page: nextPage,
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+    });
+    newArrivals = { page: result.page, tones: [...newArrivals.tones, ...result.tones] };
+    renderBrowseList(newArrivals.tones, "Yeni yuklenen capture bulunamadi.");
+    el.browseMoreButton.hidden = !result.has_more;
+  } catch (err) {
+    renderBrowseList([], typeof err === "string" ? err : "Yeni yuklenenler alinamadi.");
+    el.browseMoreButton.hidden = true;
+  }
+}
+
+async function onBrowseListClick(event) {
+  const invoke = getInvoke();
+  const toneId = Number(event.target?.closest?.("[data-tone-id]")?.dataset?.toneId);
+  if (!invoke || !toneId) return;
+  try {
+    const tone = await invoke("get_tone", {
+      toneId,
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+    });
+    el.toneDetail.hidden = false;
+    el.toneDetail.innerHTML = `
+      <article class="tone-item">
+        <div class="name">${escapeHtml(tone.title)}</div>
+        <div class="meta">${escapeHtml(tone.gear)} - ${escapeHtml(tone.platform)} - ${escapeHtml(tone.author || "-")} - ${escapeHtml(tone.license || "lisans yok")}</div>
+        <div class="meta">${escapeHtml(tone.description || "")}</div>
+        <div class="meta">Etiketler: ${escapeHtml(tone.tags.join(", ") || "-")}</div>
+        ${tone.models
+          .map((m) => `<div class="meta">${escapeHtml(m.name)} (${escapeHtml(m.size || "?")})</div>`)
+          .join("")}
+      </article>
+    `;
+  } catch (err) {
+    el.toneDetail.hidden = false;
+    el.toneDetail.textContent = typeof err === "string" ? err : "Tone detaylari alinamadi.";
+  }
+}
+
 function renderModels(models) {
   if (!models || models.length === 0) {
     el.modelList.className = "model-list empty";
@@ -131,6 +381,36 @@ function renderModels(models) {
         <div class="meta">
           ${escapeHtml(item.tone_title || "tone")} - ${escapeHtml(item.status || "unknown")} - ${item.size_mb ?? 0} MB
         </div>
+        ${item.gain_staging ? `<div class="meta">Gain: ${escapeHtml(item.gain_staging)}</div>` : ""}
+      </article>
+    `
+    )
+    .join("");
+}
+
+const WARNING_LABELS = {
+  quota_low: "Kota azaliyor",
+  degraded_mode: "Heuristik mod",
+  fallback: "Yedek yol kullanildi",
+  stage_timeout: "Zaman asimi",
+  model_skipped: "Model atlandi",
+  download_failed: "Indirme basarisiz",
+  low_confidence: "Dusuk guven",
+  incomplete_preset: "Eksik preset",
+  keep_awake: "Uyku engellenemedi",
+  config: "Ayar dosyasi",
+};
+
+function renderWarnings(warnings) {
+  const list = warnings || [];
+  el.warningPanel.hidden = list.length === 0;
+  el.warningList.innerHTML = list
+    .map(
+      (warning) => `
+      <article class="warning-item ${escapeHtml(warning.kind || "")}">
+        <div class="name">${escapeHtml(WARNING_LABELS[warning.kind] || warning.kind || "Uyari")}${warning.preset ? ` - ${escapeHtml(warning.preset)}` : ""}</div>
+        <div class="meta">${escapeHtml(warning.message || "")}</div>
+        ${warning.action ? `<div class="meta action">${escapeHtml(warning.action)}</div>` : ""}
       </article>
     `
     )
@@ -138,17 +418,77 @@ function renderModels(models) {
 }
 
 function setRunningState(running) {
-  isRunning = running;
-  el.runButton.disabled = running;
+  runningCount = Math.max(0, runningCount + (running ? 1 : -1));
+  el.runButton.disabled = runningCount >= MAX_PARALLEL_RUNS;
+  el.surpriseButton.disabled = runningCount >= MAX_PARALLEL_RUNS;
+}
+
+function formatSpeed(bps) {
+  if (!bps) return "-";
+  return bps >= 1024 * 1024 ? `${(bps / (1024 * 1024)).toFixed(1)} MB/s` : `${Math.round(bps / 1024)} KB/s`;
+}
+
+function formatEta(secs) {
+  if (secs === null || secs === undefined) return "?";
+  return secs >= 60 ? `${Math.floor(secs / 60)}dk ${secs % 60}sn` : `${secs}sn`;
+}
+
+function sleepNote(state) {
+  if (!state) return "";
+  return state.active
+    ? ` | Uyku engellendi (${state.method})`
+    : " | UYARI: uyku engellenemedi, indirme bitene kadar bilgisayari acik tut";
+}
+
+const STAGE_LABELS = {
+  analysis: "Istek analizi",
+  search: "Ton arama",
+  selection: "Ton secimi",
+  download: "Model filtreleme ve indirme",
+};
+
+function onRunProgress(event) {
+  const p = event?.payload;
+  if (!p) return;
+  if (p.started) {
+    displayedJobId = p.job_id;
+    return;
+  }
+  if (p.job_id && p.job_id !== displayedJobId) return;
+  if (typeof p.log === "string") {
+    el.logOutput.textContent += `${p.log}\n`;
+    el.logOutput.scrollTop = el.logOutput.scrollHeight;
+    return;
+  }
+  if (p.import) {
+    const phase = p.import.phase === "scan" ? "taraniyor" : "TONE3000 ile eslestiriliyor";
+    el.progressText.textContent = `Ice aktarma: ${phase} ${p.import.done}/${p.import.total}...`;
+    return;
+  }
+  if (p.stage) {
+    const step = Number.isInteger(p.stage_index) ? `${p.stage_index + 1}/${p.stage_count} ` : "";
+    el.progressText.textContent = `Asama ${step}${STAGE_LABELS[p.stage] || p.stage}...`;
+    return;
+  }
+  if (!p.file) {
+    el.progressText.textContent = p.sleep_inhibitor
+      ? `Indirmeler basliyor${sleepNote(p.sleep_inhibitor)}`
+      : "Indirmeler bitti, uyku engeli kaldirildi.";
+    return;
+  }
+  el.progressText.textContent =
+    `${p.file}: ${formatSpeed(p.file_speed_bps)}, kalan ${formatEta(p.file_eta_secs)} | ` +
+    `Toplam ${formatSpeed(p.run_speed_bps)}, tahmini bitis ${formatEta(p.run_eta_secs)}` +
+    sleepNote(p.sleep_inhibitor);
 }
 
 function getInvoke() {
   return window.__TAURI__?.core?.invoke;
 }
 
-function collectPayload() {
+function collectPayload(requireRequest = true) {
   const request = el.toneRequest.value.trim();
-  if (!request) throw new Error("Tone istegi bos olamaz.");
+  if (requireRequest && !request) throw new Error("Tone istegi bos olamaz.");
 
   const maxTones = Number(el.maxTones.value || 3);
   const maxResults = Number(el.maxResults.value || 15);
@@ -165,18 +505,30 @@ function collectPayload() {
   }
 
   return {
+    ...templateOptions,
     request,
-    outputDir: (el.outputDir.value || "./smart_downloaded_tones").trim(),
+    outputDir: el.outputDir.value.trim() || null,
     maxTones,
     maxResults,
     geminiModel,
+    uiLanguage: el.uiLanguage.value,
+    privacyMode: el.privacyMode.checked,
+    ownedPreference: el.ownedPreference.value,
+    dedupeScope: el.dedupeMode.value === "path" ? "path" : "library",
+    onLibraryMatch: el.dedupeMode.value === "download" ? "download" : "link",
+    micPreference: el.micPreference.value.trim() || null,
+    uniformFileNames: el.uniformFileNames.checked,
+    fastPlan: el.fastPlan.checked,
+    finalistDetails: el.finalistDetails.checked,
+    excludeRoomIrs: el.excludeRoomIrs.checked,
+    auditPrompts: el.auditPrompts.checked,
     tone3000ApiKey: el.tone3000Key.value.trim() || null,
     geminiApiKey: el.geminiKey.value.trim() || null,
   };
 }
 
 async function onRun() {
-  if (isRunning) return;
+  if (runningCount >= MAX_PARALLEL_RUNS) return;
 
   const invoke = getInvoke();
   if (!invoke) {
@@ -192,34 +544,475 @@ async function onRun() {
     return;
   }
 
+  await runCommand(invoke, "run_download", { payload });
+}
+
+async function onPreview() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+
+  let payload;
+  try {
+    payload = collectPayload();
+  } catch (err) {
+    setRunState("error", err.message);
+    return;
+  }
+
+  el.previewButton.disabled = true;
+  el.analysisSummary.textContent = "Istek analiz ediliyor...";
+  try {
+    const response = await invoke("preview_analysis", { payload });
+    if (!response?.ok) {
+      el.analysisSummary.textContent = response?.error || "Analiz yapilamadi.";
+      return;
+    }
+    const sourceLabel = {
+      gemini: "Gemini",
+      heuristic: "anahtar kelime (Gemini yok)",
+      keywords: "hizli plan anahtar kelimeleri",
+    }[response.source] || response.source;
+    renderAnalysis(response.analysis, undefined, `onizleme, ${sourceLabel}`);
+  } catch (err) {
+    el.analysisSummary.textContent = typeof err === "string" ? err : "Analiz yapilamadi.";
+  } finally {
+    el.previewButton.disabled = false;
+  }
+}
+
+async function onSurprise() {
+  if (runningCount >= MAX_PARALLEL_RUNS) return;
+  const invoke = getInvoke();
+  if (!invoke) return;
+
+  let payload;
+  try {
+    payload = collectPayload(false);
+  } catch (err) {
+    setRunState("error", err.message);
+    return;
+  }
+
+  const response = await runCommand(invoke, "surprise_me", {
+    genre: el.surpriseGenre.value || null,
+    payload,
+  });
+  if (response?.surprise) {
+    el.toneRequest.value = response.surprise.request;
+    el.analysisSummary.textContent = `Surpriz (${response.surprise.genre}): ${response.surprise.why}`;
+  }
+}
+
+async function runCommand(invoke, command, args) {
+  let response = null;
   setRunningState(true);
-  setRunState("running", "AI analiz ve indirme akisi calisiyor...");
+  setRunState(
+    "running",
+    runningCount > 1
+      ? `${runningCount} istek paralel calisiyor...`
+      : "AI analiz ve indirme akisi calisiyor..."
+  );
+  el.logOutput.textContent = "";
+  displayedJobId = null;
 
   try {
-    const response = await invoke("run_download", { payload });
+    response = await invoke(command, args);
     if (!response?.ok) {
-      const msg = response?.error || "Islem basarisiz oldu.";
+      const msg = describeError(response?.error, response?.error_code) || "Islem basarisiz oldu.";
       setRunState("error", msg);
       renderAnalysis(null);
       renderAiSteps([]);
       renderTones([], []);
       renderModels([]);
+      renderWarnings([]);
       el.logOutput.textContent = response?.logs || msg;
       return;
     }
 
+    lastRun = { runId: response.run_key, outputDir: args.payload?.outputDir || null };
+    el.shareButton.disabled = !response.run_key || response.downloaded_count === 0;
     renderAnalysis(response.analysis, response.pool_size, response.gemini_model);
     renderAiSteps(response.ai_steps);
+    lastRigs = response.rig_presets || [];
     renderTones(response.rig_presets, response.selected_tones);
     renderModels(response.model_items);
+    renderWarnings(response.warnings);
     el.logOutput.textContent = response.logs || "Log alinamadi.";
-    setRunState("done", `Tamamlandi. ${response.downloaded_count} model indirildi. Cikti: ${response.output_dir}`);
+    const degradedNote = response.degraded_mode
+      ? ` UYARI: Gemini kullanilamadi, heuristik mod kullanildi (${response.degraded_reason || "bilinmeyen hata"}).`
+      : "";
+    const cachedNote = response.cached ? " (Onceki ayni calistirmadan, dosyalar dogrulandi.)" : "";
+    const auditNote = response.audit_dir ? ` Gemini kayitlari: ${response.audit_dir}` : "";
+    const otherRuns = runningCount > 1 ? ` ${runningCount - 1} istek hala calisiyor.` : "";
+    const stats = response.download_stats;
+    el.progressText.textContent = stats
+      ? `${(stats.total_bytes / (1024 * 1024)).toFixed(1)} MB, ${stats.elapsed_secs}sn, ortalama ${formatSpeed(stats.average_speed_bps)}`
+      : "";
+    setRunState("done", `Tamamlandi${otherRuns ? ` (${response.request || ""})` : ""}. ${response.downloaded_count} model indirildi. Cikti: ${response.output_dir}${cachedNote}${degradedNote}${auditNote}${otherRuns}`);
   } catch (err) {
-    const msg = typeof err === "string" ? err : err?.message || "Bilinmeyen hata";
+    const msg = describeError(typeof err === "string" ? err : err?.message) || "Bilinmeyen hata";
     setRunState("error", msg);
     el.logOutput.textContent = msg;
   } finally {
     setRunningState(false);
+    refreshResumableJobs();
+    refreshLibraryStats();
+  }
+  return response;
+}
+
+async function refreshLibraryStats() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    const stats = await invoke("get_library_stats", {
+      outputDir: el.outputDir.value.trim() || null,
+    });
+    const list = (rows) => rows.map((r) => `${escapeHtml(r.name)} (${r.count})`).join(", ") || "-";
+    el.libraryStats.innerHTML = `
+      <span class="meta-chip">Preset: ${stats.presets}</span>
+      <span class="meta-chip">Model: ${stats.models}</span>
+      <span class="meta-chip">Disk: ${stats.disk_usage_mb} MB</span>
+      <div class="meta">Gear: ${list(stats.by_gear)}</div>
+      <div class="meta">Platform: ${list(stats.by_platform)}</div>
+      <div class="meta">Yapimcilar: ${list(stats.by_creator)}</div>
+      <div class="meta">En cok kullanilan cab'ler: ${list(stats.most_used_cabs)}</div>
+    `;
+  } catch (err) {
+    el.libraryStats.textContent = typeof err === "string" ? err : "Istatistik alinamadi.";
+  }
+}
+
+async function cleanupOutputDir() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    const report = await invoke("cleanup_output_dir", {
+      outputDir: el.outputDir.value.trim() || null,
+    });
+    if (report.removed.length > 0 || report.failed.length > 0) {
+      el.logOutput.textContent = [
+        `Yarim kalan ${report.removed.length} dosya temizlendi (${Math.round(report.freed_bytes / 1024)} KB).`,
+        ...report.removed,
+        ...report.failed.map((f) => `Silinemedi: ${f}`),
+      ].join("\n");
+    }
+  } catch (_err) {
+    // Cleanup is best-effort; the run itself never depends on it.
+  }
+}
+
+async function refreshResumableJobs() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  let jobs = [];
+  try {
+    jobs = await invoke("list_resumable_jobs");
+  } catch (_err) {
+    jobs = [];
+  }
+  el.resumePanel.hidden = jobs.length === 0;
+  el.resumeList.innerHTML = jobs
+    .map(
+      (job) => `
+      <article class="tone-item">
+        <div class="name">${escapeHtml(job.request)}</div>
+        <div class="meta">${escapeHtml(job.status)} - ${escapeHtml(job.updated_at)}${job.has_plan ? " - plan kayitli" : ""}</div>
+        <div class="actions">
+          <button class="btn btn-primary" data-resume="${escapeHtml(job.id)}">Devam Et</button>
+          <button class="btn btn-ghost" data-discard="${escapeHtml(job.id)}">Vazgec</button>
+        </div>
+      </article>
+    `
+    )
+    .join("");
+}
+
+async function onResumeListClick(event) {
+  const invoke = getInvoke();
+  const resumeId = event.target?.dataset?.resume;
+  const discardId = event.target?.dataset?.discard;
+  if (!invoke || runningCount >= MAX_PARALLEL_RUNS) return;
+  if (discardId) {
+    await invoke("discard_job", { jobId: discardId });
+    refreshResumableJobs();
+    return;
+  }
+  if (resumeId) {
+    await runCommand(invoke, "resume_job", {
+      jobId: resumeId,
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+      geminiApiKey: el.geminiKey.value.trim() || null,
+    });
+  }
+}
+
+async function refreshCallEstimate() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  const maxTones = Number(el.maxTones.value || 3);
+  try {
+    const estimate = await invoke("estimate_llm_calls", {
+      maxTones: Number.isNaN(maxTones) ? null : maxTones,
+      fastPlan: el.fastPlan.checked,
+      finalistDetails: el.finalistDetails.checked,
+    });
+    el.callEstimate.textContent = `Tahmini Gemini cagrisi: ${estimate.min_calls}-${estimate.max_calls}`;
+  } catch (_err) {
+    el.callEstimate.textContent = "";
+  }
+}
+
+async function refreshAccountInfo() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  const maxTones = Number(el.maxTones.value || 3);
+  try {
+    const account = await invoke("get_account_info", {
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+      maxTones: Number.isNaN(maxTones) ? null : maxTones,
+    });
+    const quota =
+      account.downloads_remaining === null
+        ? ""
+        : ` - kalan indirme: ${account.downloads_remaining}${account.download_limit === null ? "" : `/${account.download_limit}`}`;
+    const warning = account.quota_warning ? ` - Uyari: ${account.quota_warning}` : "";
+    el.accountInfo.textContent = `TONE3000: ${account.username || "-"} (${account.plan || "plan bilinmiyor"})${quota}${warning}`;
+  } catch (_err) {
+    el.accountInfo.textContent = "";
+  }
+}
+
+async function refreshKeysLock() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    const policy = await invoke("get_key_policy");
+    el.keysUnlockRow.hidden = !policy.keys_file_locked;
+  } catch (_err) {
+    el.keysUnlockRow.hidden = true;
+  }
+}
+
+async function onUnlockKeys() {
+  const invoke = getInvoke();
+  const passphrase = el.keysPassphrase.value;
+  if (!invoke || !passphrase) return;
+  try {
+    const names = await invoke("unlock_keys", { passphrase });
+    el.keysPassphrase.value = "";
+    el.keysUnlockRow.hidden = true;
+    el.progressText.textContent = `Anahtar dosyasi acildi: ${names.join(", ")}`;
+    refreshAccountInfo();
+  } catch (err) {
+    el.progressText.textContent = typeof err === "string" ? err : "Anahtar dosyasi acilamadi.";
+  }
+}
+
+async function loadTemplates() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    const groups = await invoke("list_request_templates");
+    el.surpriseGenre.insertAdjacentHTML(
+      "beforeend",
+      groups.map((group) => `<option>${escapeHtml(group.genre)}</option>`).join("")
+    );
+    el.templateSelect.insertAdjacentHTML(
+      "beforeend",
+      groups
+        .map(
+          (group) => `
+        <optgroup label="${escapeHtml(group.genre)}">
+          ${group.templates
+            .map((t) => `<option value="${escapeHtml(t.id)}">${escapeHtml(t.era)} - ${escapeHtml(t.title)}</option>`)
+            .join("")}
+        </optgroup>
+      `
+        )
+        .join("")
+    );
+  } catch (_err) {
+    el.templateSelect.hidden = true;
+  }
+}
+
+async function onTemplateSelect() {
+  const invoke = getInvoke();
+  const id = el.templateSelect.value;
+  if (!invoke || !id) return;
+  try {
+    const template = await invoke("get_template", { id });
+    el.toneRequest.value = template.request;
+    templateOptions = template.options || {};
+    if (templateOptions.maxTones) {
+      el.maxTones.value = templateOptions.maxTones;
+      refreshCallEstimate();
+    }
+  } catch (err) {
+    setRunState("error", typeof err === "string" ? err : "Ornek istek yuklenemedi.");
+  }
+}
+
+async function refreshDailyTone() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    dailyTone = await invoke("get_daily_tone", {
+      tone3000ApiKey: el.tone3000Key.value.trim() || null,
+    });
+  } catch (_err) {
+    return;
+  }
+  renderDailyTone();
+}
+
+// Sent by the backend's daily check, also while the window is hidden to the tray.
+function onDailyToneEvent(event) {
+  if (!event?.payload) return;
+  dailyTone = event.payload;
+  renderDailyTone();
+}
+
+function renderDailyTone() {
+  const tone = dailyTone.tone;
+  el.dailyPanel.hidden = !tone;
+  if (!tone) return;
+  el.dailyTone.innerHTML = `
+    <article class="tone-item">
+      <div class="name">${escapeHtml(tone.title)}</div>
+      <div class="meta">${escapeHtml(dailyTone.date)} - ${escapeHtml(dailyTone.theme.genre)}: ${escapeHtml(dailyTone.theme.title)}</div>
+      <div class="meta">${escapeHtml(tone.author || "-")} - ${tone.downloads_count ?? 0} indirme</div>
+    </article>
+  `;
+  if (localStorage.getItem("dailyToneNotified") !== dailyTone.date) {
+    localStorage.setItem("dailyToneNotified", dailyTone.date);
+    notifyDailyTone(tone.title);
+  }
+}
+
+// The backend check needs a key from the env or keys file; a key typed into the UI is only
+// used here, when the window is shown again on a new day.
+function refreshDailyToneIfStale() {
+  if (document.hidden || !dailyTone) return;
+  if (dailyTone.date !== new Date().toISOString().slice(0, 10)) refreshDailyTone();
+}
+
+async function notifyDailyTone(title) {
+  if (!("Notification" in window)) return;
+  if (Notification.permission === "default") await Notification.requestPermission();
+  if (Notification.permission === "granted") new Notification("Gunun tonu", { body: title });
+}
+
+async function onDailyDownload() {
+  const invoke = getInvoke();
+  if (!invoke || !dailyTone || runningCount >= MAX_PARALLEL_RUNS) return;
+  let payload;
+  try {
+    payload = collectPayload(false);
+  } catch (err) {
+    setRunState("error", err.message);
+    return;
+  }
+  payload.request = dailyTone.request;
+  await runCommand(invoke, "download_daily_tone", {
+    toneId: dailyTone.tone.id,
+    payload,
+    subdir: "daily",
+  });
+}
+
+function onRequestEdited() {
+  templateOptions = {};
+  el.templateSelect.value = "";
+  clearTimeout(suggestTimer);
+  suggestTimer = setTimeout(refreshSuggestions, 200);
+}
+
+async function refreshSuggestions() {
+  const invoke = getInvoke();
+  const prefix = el.toneRequest.value.trim();
+  if (!invoke || prefix.length < 2 || prefix.length > 40) {
+    el.requestSuggestions.innerHTML = "";
+    return;
+  }
+  try {
+    const suggestions = await invoke("suggest_queries", {
+      prefix,
+      outputDir: el.outputDir.value.trim() || null,
+    });
+    el.requestSuggestions.innerHTML = suggestions
+      .map(
+        (s) =>
+          `<button class="meta-chip" data-suggestion="${escapeHtml(s.text)}" title="${escapeHtml(s.source)}">${escapeHtml(s.text)}</button>`
+      )
+      .join("");
+  } catch (_err) {
+    el.requestSuggestions.innerHTML = "";
+  }
+}
+
+function onSuggestionClick(event) {
+  const text = event.target?.dataset?.suggestion;
+  if (!text) return;
+  el.toneRequest.value = text;
+  el.requestSuggestions.innerHTML = "";
+}
+
+async function onShare() {
+  const invoke = getInvoke();
+  if (!invoke || !lastRun) return;
+  try {
+    const markdown = await invoke("export_reasoning", { ...lastRun, format: "markdown" });
+    await navigator.clipboard.writeText(markdown);
+    el.progressText.textContent = "Rig aciklamasi panoya kopyalandi.";
+  } catch (err) {
+    el.progressText.textContent = typeof err === "string" ? err : "Aciklama kopyalanamadi.";
+  }
+}
+
+async function loadDefaultOutputDir() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    const saved = await invoke("get_default_output_dir");
+    if (saved) el.outputDir.value = saved;
+  } catch (_err) {
+    // Keep the built-in default.
+  }
+}
+
+async function onSetDefaultOutputDir() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    const report = await invoke("set_default_output_dir", { path: el.outputDir.value.trim() || null });
+    el.outputDir.value = report.path;
+    const free = report.free_mb == null ? "" : ` (${report.free_mb} MB bos)`;
+    el.progressText.textContent = `Varsayilan output klasoru kaydedildi: ${report.path}${free}`;
+  } catch (err) {
+    el.progressText.textContent = typeof err === "string" ? err : "Klasor kaydedilemedi.";
+  }
+}
+
+async function loadTrayMode() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    el.trayMode.checked = await invoke("get_tray_mode");
+  } catch (_err) {
+    el.trayMode.checked = false;
+  }
+}
+
+async function onTrayModeChange() {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  try {
+    el.trayMode.checked = await invoke("set_tray_mode", { enabled: el.trayMode.checked });
+  } catch (err) {
+    el.trayMode.checked = !el.trayMode.checked;
+    setRunState("error", typeof err === "string" ? err : "Tepsi modu kaydedilemedi.");
   }
 }
 
@@ -233,7 +1026,41 @@ function init() {
   renderTones([], []);
   renderModels([]);
   el.runButton.addEventListener("click", onRun);
+  el.previewButton.addEventListener("click", onPreview);
+  el.templateSelect.addEventListener("change", onTemplateSelect);
+  el.surpriseButton.addEventListener("click", onSurprise);
+  el.dailyDownloadButton.addEventListener("click", onDailyDownload);
+  el.trayMode.addEventListener("change", onTrayModeChange);
+  el.trendingButton.addEventListener("click", onTrending);
+  el.browseList.addEventListener("click", onBrowseListClick);
+  el.selectedToneList.addEventListener("click", onRigListClick);
+  el.newArrivalsButton.addEventListener("click", () => loadNewArrivals(1));
+  el.browseMoreButton.addEventListener("click", () => loadNewArrivals(newArrivals.page + 1));
+  el.toneRequest.addEventListener("input", onRequestEdited);
+  el.requestSuggestions.addEventListener("click", onSuggestionClick);
   el.clearLogsButton.addEventListener("click", onClearLogs);
+  el.shareButton.addEventListener("click", onShare);
+  el.maxTones.addEventListener("input", refreshCallEstimate);
+  el.fastPlan.addEventListener("change", refreshCallEstimate);
+  el.finalistDetails.addEventListener("change", refreshCallEstimate);
+  el.maxTones.addEventListener("change", refreshAccountInfo);
+  el.tone3000Key.addEventListener("change", refreshAccountInfo);
+  el.resumeList.addEventListener("click", onResumeListClick);
+  el.libraryRefreshButton.addEventListener("click", refreshLibraryStats);
+  el.defaultOutputButton.addEventListener("click", onSetDefaultOutputDir);
+  el.keysUnlockButton.addEventListener("click", onUnlockKeys);
+  refreshCallEstimate();
+  refreshKeysLock().then(refreshAccountInfo);
+  loadTemplates();
+  loadTrayMode();
+  refreshDailyTone();
+  document.addEventListener("visibilitychange", refreshDailyToneIfStale);
+  window.__TAURI__?.event?.listen("daily-tone", onDailyToneEvent);
+  refreshResumableJobs();
+  loadDefaultOutputDir()
+    .then(cleanupOutputDir)
+    .then(refreshLibraryStats);
+  window.__TAURI__?.event?.listen("run-progress", onRunProgress);
 }
 
 init();